@@ -1,10 +1,17 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
 use color_eyre::Result;
 use crossterm::event::{self, Event, KeyCode, KeyEvent};
+
+/// Upper bound on the counter history buffer; matches a comfortably wide
+/// terminal so the sparkline has enough samples to fill its width.
+const HISTORY_CAPACITY: usize = 200;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Gauge, LineGauge, List, ListItem, ListState, Paragraph, Sparkline, Tabs},
     DefaultTerminal, Frame,
 };
 
@@ -13,51 +20,88 @@ struct App {
     counter: u8,
     should_quit: bool,
     items: Vec<String>,
-    selected_index: usize,
+    list_state: ListState,
+    tick_rate: Duration,
+    titles: Vec<String>,
+    tab_index: usize,
+    history: VecDeque<u64>,
+    compact_gauge: bool,
 }
 
 impl App {
     fn new() -> Self {
+        let items = vec![
+            "Item 1".to_string(),
+            "Item 2".to_string(),
+            "Item 3".to_string(),
+            "Item 4".to_string(),
+            "Item 5".to_string(),
+        ];
+        let mut list_state = ListState::default();
+        list_state.select(if items.is_empty() { None } else { Some(0) });
         Self {
             counter: 0,
             should_quit: false,
-            items: vec![
-                "Item 1".to_string(),
-                "Item 2".to_string(),
-                "Item 3".to_string(),
-                "Item 4".to_string(),
-                "Item 5".to_string(),
-            ],
-            selected_index: 0,
+            items,
+            list_state,
+            tick_rate: Duration::from_millis(250),
+            titles: vec!["List".to_string(), "Stats".to_string(), "Help".to_string()],
+            tab_index: 0,
+            history: VecDeque::with_capacity(HISTORY_CAPACITY),
+            compact_gauge: false,
         }
     }
 
+    fn next_tab(&mut self) {
+        self.tab_index = (self.tab_index + 1) % self.titles.len();
+    }
+
+    fn previous_tab(&mut self) {
+        self.tab_index = (self.tab_index + self.titles.len() - 1) % self.titles.len();
+    }
+
     fn tick(&mut self) {
         self.counter = self.counter.saturating_add(1);
+        if self.history.len() == HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+        self.history.push_back(self.counter as u64);
     }
 
     fn handle_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
             KeyCode::Char(' ') => self.tick(),
+            KeyCode::Right | KeyCode::Tab => self.next_tab(),
+            KeyCode::Left | KeyCode::BackTab => self.previous_tab(),
             KeyCode::Up => {
-                if self.selected_index > 0 {
-                    self.selected_index -= 1;
+                if let Some(s) = self.list_state.selected() {
+                    if s > 0 {
+                        self.list_state.select(Some(s - 1));
+                    }
                 }
             }
             KeyCode::Down => {
-                if self.selected_index < self.items.len() - 1 {
-                    self.selected_index += 1;
+                if let Some(s) = self.list_state.selected() {
+                    if s + 1 < self.items.len() {
+                        self.list_state.select(Some(s + 1));
+                    }
                 }
             }
+            KeyCode::Char('g') => self.compact_gauge = !self.compact_gauge,
             KeyCode::Char('a') => {
                 self.items.push(format!("New Item {}", self.items.len() + 1));
+                if self.list_state.selected().is_none() {
+                    self.list_state.select(Some(0));
+                }
             }
             KeyCode::Char('d') => {
-                if !self.items.is_empty() {
-                    self.items.remove(self.selected_index);
-                    if self.selected_index >= self.items.len() && self.selected_index > 0 {
-                        self.selected_index -= 1;
+                if let Some(s) = self.list_state.selected() {
+                    self.items.remove(s);
+                    if self.items.is_empty() {
+                        self.list_state.select(None);
+                    } else if s >= self.items.len() {
+                        self.list_state.select(Some(self.items.len() - 1));
                     }
                 }
             }
@@ -68,31 +112,53 @@ impl App {
 
 fn main() -> Result<()> {
     color_eyre::install()?;
+    install_panic_hook();
     let terminal = ratatui::init();
     let result = run(terminal);
     ratatui::restore();
     result
 }
 
+/// Restore the terminal before the previously installed hook (the one
+/// `color_eyre::install` set) runs so a panic inside
+/// `render`/`handle_key` doesn't leave the terminal in raw mode or the
+/// alternate screen, and `color_eyre`'s report still prints cleanly.
+fn install_panic_hook() {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        ratatui::restore();
+        hook(info);
+    }));
+}
+
 fn run(mut terminal: DefaultTerminal) -> Result<()> {
     let mut app = App::new();
-    
+    let mut last_tick = Instant::now();
+
     loop {
-        terminal.draw(|frame| render(&app, frame))?;
-        
-        if let Event::Key(key) = event::read()? {
-            app.handle_key(key);
+        terminal.draw(|frame| render(&mut app, frame))?;
+
+        let timeout = app.tick_rate.saturating_sub(last_tick.elapsed());
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                app.handle_key(key);
+            }
+        }
+
+        if last_tick.elapsed() >= app.tick_rate {
+            app.tick();
+            last_tick = Instant::now();
         }
-        
+
         if app.should_quit {
             break;
         }
     }
-    
+
     Ok(())
 }
 
-fn render(app: &App, frame: &mut Frame) {
+fn render(app: &mut App, frame: &mut Frame) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
@@ -108,21 +174,24 @@ fn render(app: &App, frame: &mut Frame) {
     render_footer(app, frame, chunks[2]);
 }
 
-fn render_header(_app: &App, frame: &mut Frame, area: Rect) {
-    let header = Paragraph::new(vec![
-        Line::from(vec![
-            Span::raw("Welcome to "),
-            Span::styled("Ratatui", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-            Span::raw(" Example!"),
-        ]),
-    ])
-    .block(Block::default().borders(Borders::ALL).title("Header"))
-    .alignment(Alignment::Center);
-    
-    frame.render_widget(header, area);
+fn render_header(app: &App, frame: &mut Frame, area: Rect) {
+    let tabs = Tabs::new(app.titles.iter().map(|t| Line::from(t.clone())).collect::<Vec<_>>())
+        .block(Block::default().borders(Borders::ALL).title("Ratatui Example"))
+        .select(app.tab_index)
+        .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    frame.render_widget(tabs, area);
 }
 
-fn render_body(app: &App, frame: &mut Frame, area: Rect) {
+fn render_body(app: &mut App, frame: &mut Frame, area: Rect) {
+    match app.tab_index {
+        0 => render_list_tab(app, frame, area),
+        1 => render_stats(app, frame, area),
+        _ => render_help(app, frame, area),
+    }
+}
+
+fn render_list_tab(app: &mut App, frame: &mut Frame, area: Rect) {
     let body_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
@@ -132,43 +201,65 @@ fn render_body(app: &App, frame: &mut Frame, area: Rect) {
     render_info(app, frame, body_chunks[1]);
 }
 
-fn render_list(app: &App, frame: &mut Frame, area: Rect) {
+fn render_stats(app: &App, frame: &mut Frame, area: Rect) {
+    let stats = Paragraph::new(vec![
+        Line::from(format!("Counter: {}", app.counter)),
+        Line::from(format!("Items: {}", app.items.len())),
+        Line::from(format!(
+            "Selected: {}",
+            app.list_state.selected().map_or_else(|| "none".to_string(), |s| s.to_string())
+        )),
+    ])
+    .block(Block::default().borders(Borders::ALL).title("Stats"))
+    .style(Style::default().fg(Color::White));
+
+    frame.render_widget(stats, area);
+}
+
+fn render_help(_app: &App, frame: &mut Frame, area: Rect) {
+    let help = Paragraph::new(vec![
+        Line::from("←/→ or Tab/BackTab : switch tabs"),
+        Line::from("↑/↓                : navigate the list"),
+        Line::from("a                  : add an item"),
+        Line::from("d                  : delete the selected item"),
+        Line::from("Space              : increment the counter"),
+        Line::from("g                  : toggle the compact gauge"),
+        Line::from("q / Esc            : quit"),
+    ])
+    .block(Block::default().borders(Borders::ALL).title("Help"))
+    .style(Style::default().fg(Color::White));
+
+    frame.render_widget(help, area);
+}
+
+fn render_list(app: &mut App, frame: &mut Frame, area: Rect) {
     let items: Vec<ListItem> = app
         .items
         .iter()
-        .enumerate()
-        .map(|(i, item)| {
-            let content = if i == app.selected_index {
-                Line::from(vec![
-                    Span::raw("> "),
-                    Span::styled(item, Style::default().fg(Color::Yellow)),
-                ])
-            } else {
-                Line::from(vec![
-                    Span::raw("  "),
-                    Span::raw(item),
-                ])
-            };
-            ListItem::new(content)
-        })
+        .map(|item| ListItem::new(Line::from(Span::raw(item.clone()))))
         .collect();
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title("List (↑/↓ to navigate, 'a' to add, 'd' to delete)"))
-        .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+        .highlight_symbol("> ")
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
 
-    frame.render_widget(list, area);
+    frame.render_stateful_widget(list, area, &mut app.list_state);
 }
 
 fn render_info(app: &App, frame: &mut Frame, area: Rect) {
+    let gauge_height = if app.compact_gauge { 1 } else { 3 };
     let info_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(5), Constraint::Min(3)])
+        .constraints([Constraint::Length(5), Constraint::Min(3), Constraint::Length(gauge_height)])
         .split(area);
 
     let info = Paragraph::new(vec![
         Line::from(format!("Counter: {}", app.counter)),
-        Line::from(format!("Selected: {}", app.selected_index)),
+        Line::from(format!(
+            "Selected: {}",
+            app.list_state.selected().map_or_else(|| "none".to_string(), |s| s.to_string())
+        )),
         Line::from(format!("Items: {}", app.items.len())),
     ])
     .block(Block::default().borders(Borders::ALL).title("Info"))
@@ -176,14 +267,34 @@ fn render_info(app: &App, frame: &mut Frame, area: Rect) {
 
     frame.render_widget(info, info_chunks[0]);
 
-    let progress = app.counter as f64 / 100.0;
-    let gauge = Gauge::default()
-        .block(Block::default().borders(Borders::ALL).title("Progress"))
-        .gauge_style(Style::default().fg(Color::Green))
-        .percent((progress * 100.0) as u16)
-        .label(format!("{}%", (progress * 100.0) as u16));
+    let history: Vec<u64> = app.history.iter().copied().collect();
+    let max = history.iter().copied().max().unwrap_or(100).max(1);
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title("History"))
+        .data(&history)
+        .max(max)
+        .style(Style::default().fg(Color::Green));
+
+    frame.render_widget(sparkline, info_chunks[1]);
+
+    let progress = (app.counter as f64 / 100.0).clamp(0.0, 1.0);
+    let percent = (progress * 100.0) as u16;
+    if app.compact_gauge {
+        let gauge = LineGauge::default()
+            .filled_style(Style::default().fg(Color::Green))
+            .ratio(progress)
+            .label(format!("Progress {percent}%"));
 
-    frame.render_widget(gauge, info_chunks[1]);
+        frame.render_widget(gauge, info_chunks[2]);
+    } else {
+        let gauge = Gauge::default()
+            .block(Block::default().borders(Borders::ALL).title("Progress"))
+            .gauge_style(Style::default().fg(Color::Green))
+            .percent(percent)
+            .label(format!("{percent}%"));
+
+        frame.render_widget(gauge, info_chunks[2]);
+    }
 }
 
 fn render_footer(_app: &App, frame: &mut Frame, area: Rect) {